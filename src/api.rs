@@ -0,0 +1,28 @@
+use anyhow::{anyhow, Result};
+use solana_sdk::pubkey::Pubkey;
+
+const REMOTE_VERIFIER_URL: &str = "https://verify.osec.io/api/v1/verify";
+
+/// Notifies the hosted verification service that a program buffer is ready to be
+/// independently rebuilt and checked. This is best-effort: the upload itself already
+/// succeeded on-chain, so a failure here is reported but never rolled back.
+pub fn send_job_to_remote(program_id: &Pubkey) -> Result<()> {
+    let client = reqwest::blocking::Client::new();
+    let response = client
+        .post(REMOTE_VERIFIER_URL)
+        .json(&serde_json::json!({ "program_id": program_id.to_string() }))
+        .send()
+        .map_err(|e| anyhow!("Failed to reach remote verifier: {e}"))?;
+
+    if !response.status().is_success() {
+        return Err(anyhow!(
+            "Remote verifier rejected the job: {}",
+            response.status()
+        ));
+    }
+
+    println!(
+        "Submitted {program_id} to the remote verifier, track status at {REMOTE_VERIFIER_URL}"
+    );
+    Ok(())
+}
@@ -0,0 +1,155 @@
+use anyhow::{anyhow, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use sha2::{Digest, Sha256};
+use std::{
+    fs::File,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// Downloads a program artifact from `url` into a fresh temp directory, checking its
+/// sha256 against `expected_sha256` as it streams, then unpacks it (if it's an archive)
+/// and returns the path to the `.so` ready for `solana_program::upload_program`.
+pub fn download_and_verify(
+    url: &str,
+    expected_sha256: &str,
+    terminated: &Arc<AtomicBool>,
+) -> Result<PathBuf> {
+    if !url.starts_with("https://") {
+        return Err(anyhow!("Refusing to download over a non-HTTPS url: {url}"));
+    }
+
+    let response = reqwest::blocking::get(url)?;
+    if !response.status().is_success() {
+        return Err(anyhow!("Download failed with status {}", response.status()));
+    }
+    let total_len = response.content_length().unwrap_or(0);
+
+    // Kept on disk after return: the caller still has to read the `.so` from it.
+    let dir = tempfile::tempdir()?.into_path();
+    let file_name = url.rsplit('/').next().unwrap_or("artifact");
+    let download_path = dir.join(file_name);
+
+    let pb = ProgressBar::new(total_len);
+    pb.set_style(
+        ProgressStyle::with_template("{bar:40.cyan/blue} {bytes}/{total_bytes} {msg}")
+            .unwrap_or_else(|_| ProgressStyle::default_bar()),
+    );
+
+    let mut hasher = Sha256::new();
+    let mut out_file = File::create(&download_path)?;
+    let mut reader = response;
+    let mut buf = [0u8; 8192];
+    loop {
+        if terminated.load(Ordering::Relaxed) {
+            pb.abandon_with_message("aborted");
+            return Err(anyhow!("Download interrupted by signal"));
+        }
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+        out_file.write_all(&buf[..n])?;
+        pb.inc(n as u64);
+    }
+    pb.finish_with_message("downloaded");
+
+    let actual_sha256 = hex::encode(hasher.finalize());
+    if actual_sha256 != expected_sha256 {
+        return Err(anyhow!(
+            "sha256 mismatch: expected {expected_sha256}, got {actual_sha256}"
+        ));
+    }
+
+    extract_so(&download_path, &dir)
+}
+
+/// The supported shapes a downloaded artifact can take, dispatched on by file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArtifactFormat {
+    RawSo,
+    TarBz2,
+    TarGz,
+}
+
+fn classify_artifact(file_name: &str) -> Result<ArtifactFormat> {
+    if file_name.ends_with(".so") {
+        Ok(ArtifactFormat::RawSo)
+    } else if file_name.ends_with(".tar.bz2") {
+        Ok(ArtifactFormat::TarBz2)
+    } else if file_name.ends_with(".tar.gz") {
+        Ok(ArtifactFormat::TarGz)
+    } else {
+        Err(anyhow!(
+            "Unsupported artifact format for {file_name}, expected .so, .tar.bz2 or .tar.gz"
+        ))
+    }
+}
+
+/// Returns `archive_path` itself if it's already a `.so`, otherwise unpacks the
+/// supported archive formats and locates the single `.so` inside.
+fn extract_so(archive_path: &Path, extract_dir: &Path) -> Result<PathBuf> {
+    let file_name = archive_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("");
+
+    match classify_artifact(file_name)? {
+        ArtifactFormat::RawSo => return Ok(archive_path.to_path_buf()),
+        ArtifactFormat::TarBz2 => {
+            let decoder = bzip2::read::BzDecoder::new(File::open(archive_path)?);
+            tar::Archive::new(decoder).unpack(extract_dir)?;
+        }
+        ArtifactFormat::TarGz => {
+            let decoder = flate2::read::GzDecoder::new(File::open(archive_path)?);
+            tar::Archive::new(decoder).unpack(extract_dir)?;
+        }
+    }
+
+    find_so(extract_dir)
+}
+
+fn find_so(dir: &Path) -> Result<PathBuf> {
+    for entry in walkdir_so(dir)? {
+        if entry.extension().map(|ext| ext == "so").unwrap_or(false) {
+            return Ok(entry);
+        }
+    }
+    Err(anyhow!("No .so file found after unpacking {}", dir.display()))
+}
+
+fn walkdir_so(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut out = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_dir() {
+            out.extend(walkdir_so(&path)?);
+        } else {
+            out.push(path);
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_artifact_dispatches_on_extension() {
+        assert_eq!(classify_artifact("program.so").unwrap(), ArtifactFormat::RawSo);
+        assert_eq!(classify_artifact("release.tar.bz2").unwrap(), ArtifactFormat::TarBz2);
+        assert_eq!(classify_artifact("release.tar.gz").unwrap(), ArtifactFormat::TarGz);
+    }
+
+    #[test]
+    fn classify_artifact_rejects_unknown_extension() {
+        assert!(classify_artifact("release.zip").is_err());
+    }
+}
@@ -0,0 +1,25 @@
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+
+/// Maps a Solana toolchain version string (as found in a program's `Cargo.lock` /
+/// `rust-toolchain.toml`) to the pinned Docker image used to produce a deterministic,
+/// reproducible build of that program.
+///
+/// Keeping this as an explicit table (rather than deriving a tag on the fly) means a
+/// version we haven't vetted never silently falls through to "latest".
+pub static IMAGE_MAP: Lazy<HashMap<&'static str, &'static str>> = Lazy::new(|| {
+    let mut m = HashMap::new();
+    m.insert("v1.13.5", "ellipsislabs/solana:1.13.5");
+    m.insert("v1.13.6", "ellipsislabs/solana:1.13.6");
+    m.insert("v1.14.10", "ellipsislabs/solana:1.14.10");
+    m.insert("v1.14.16", "ellipsislabs/solana:1.14.16");
+    m.insert("v1.14.17", "ellipsislabs/solana:1.14.17");
+    m.insert("v1.15.0", "ellipsislabs/solana:1.15.0");
+    m.insert("v1.16.0", "ellipsislabs/solana:1.16.0");
+    m
+});
+
+/// Returns the pinned Docker image tag for a given toolchain version, if we have one.
+pub fn get_image(version: &str) -> Option<&'static str> {
+    IMAGE_MAP.get(version).copied()
+}
@@ -11,6 +11,7 @@ use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
     bpf_loader_upgradeable::{self, UpgradeableLoaderState},
     pubkey::Pubkey,
+    signature::Signer,
 };
 use std::{
     fs::File,
@@ -25,31 +26,78 @@ use std::{
 use uuid::Uuid;
 
 mod api;
+mod download;
 mod image_config;
+mod serve;
 mod solana_program;
+mod verify;
+mod verify_manifest;
+mod workspace;
 
-use image_config::IMAGE_MAP;
 use crate::{
-    api::send_job_to_remote,
+    download::download_and_verify,
+    serve::{serve, ServeState},
     solana_program::{process_close, upload_program},
+    verify::verify_program,
+    verify_manifest::{publish_manifest, UpdateManifest},
+    workspace::{discover_programs, find_workspace_root},
 };
 
 const MAINNET_GENESIS_HASH: &str = "5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d";
 
-fn get_network_url(network_str: &str) -> &str {
-    match network_str {
-        "devnet" | "dev" | "d" => "https://api.devnet.solana.com",
-        "mainnet" | "main" | "m" => "https://api.mainnet-beta.solana.com",
-        _ => "https://api.devnet.solana.com",
+/// Resolves the `--network` flag to an RPC URL and whether the operator explicitly
+/// asked for mainnet. Accepts the existing aliases, `testnet`/`localnet`, and any raw
+/// `http(s)://` URL; when `network` is omitted entirely we respect the user's existing
+/// Solana CLI config instead of silently defaulting to devnet.
+fn resolve_network_url(network: Option<&str>, config: &Config) -> Result<(String, bool)> {
+    match network {
+        Some(url) if url.starts_with("http://") || url.starts_with("https://") => {
+            Ok((url.to_string(), false))
+        }
+        Some("mainnet") | Some("main") | Some("m") => {
+            Ok(("https://api.mainnet-beta.solana.com".to_string(), true))
+        }
+        Some("devnet") | Some("dev") | Some("d") => {
+            Ok(("https://api.devnet.solana.com".to_string(), false))
+        }
+        Some("testnet") | Some("test") | Some("t") => {
+            Ok(("https://api.testnet.solana.com".to_string(), false))
+        }
+        Some("localnet") | Some("local") | Some("l") => {
+            Ok(("http://127.0.0.1:8899".to_string(), false))
+        }
+        Some(other) => Err(anyhow!(
+            "Unrecognized --network '{other}', expected devnet/testnet/mainnet/localnet or an http(s):// URL"
+        )),
+        None => {
+            let is_mainnet = config.json_rpc_url.contains("mainnet-beta");
+            Ok((config.json_rpc_url.clone(), is_mainnet))
+        }
+    }
+}
+
+/// Refuses to proceed if the operator asked for mainnet but the endpoint's genesis hash
+/// says otherwise, so a misconfigured `--network`/CLI config can't silently send a
+/// mainnet-intended upload or close to the wrong cluster.
+fn guard_mainnet_genesis(rpc_client: &RpcClient, expects_mainnet: bool) -> Result<()> {
+    if !expects_mainnet {
+        return Ok(());
     }
+    let genesis_hash = rpc_client.get_genesis_hash()?;
+    if genesis_hash.to_string() != MAINNET_GENESIS_HASH {
+        return Err(anyhow!(
+            "Refusing to proceed: expected mainnet (genesis {MAINNET_GENESIS_HASH}) but connected endpoint reports {genesis_hash}"
+        ));
+    }
+    Ok(())
 }
 
 #[derive(Parser, Debug)]
 #[command(name = "solana-verifiable-build")]
 #[command(about = "Tool for verifying and uploading Solana programs", long_about = None)]
 struct Cli {
-    #[arg(short, long, default_value = "devnet")]
-    network: String,
+    #[arg(short, long)]
+    network: Option<String>,
 
     #[command(subcommand)]
     command: Commands,
@@ -65,6 +113,40 @@ enum Commands {
         #[arg(short, long)]
         program_id: String,
     },
+    Verify {
+        #[arg(short, long)]
+        program_id: String,
+        #[arg(short, long)]
+        source_path: PathBuf,
+    },
+    PublishManifest {
+        #[arg(short, long)]
+        program_id: String,
+        #[arg(short, long)]
+        source_git_url: String,
+        #[arg(short, long)]
+        commit_hash: String,
+        #[arg(short, long)]
+        docker_image_tag: String,
+        #[arg(short, long)]
+        build_sha256: String,
+    },
+    Download {
+        #[arg(short, long)]
+        url: String,
+        #[arg(short = 's', long)]
+        sha256: String,
+    },
+    UploadAll {
+        #[arg(short, long)]
+        repo_path: PathBuf,
+    },
+    Serve {
+        #[arg(short, long, default_value = "127.0.0.1:8080")]
+        listen_addr: String,
+        #[arg(short, long)]
+        artifact_dir: PathBuf,
+    },
 }
 
 fn setup_signal_handler(terminated: Arc<AtomicBool>) {
@@ -79,10 +161,10 @@ fn setup_signal_handler(terminated: Arc<AtomicBool>) {
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
-    let rpc_url = get_network_url(&cli.network);
-    let rpc_client = RpcClient::new(rpc_url.to_string());
-
     let config = Config::load(CONFIG_FILE)?;
+    let (rpc_url, expects_mainnet) = resolve_network_url(cli.network.as_deref(), &config)?;
+    let rpc_client = RpcClient::new(rpc_url.clone());
+
     let payer = solana_sdk::signature::read_keypair_file(&config.keypair_path)
         .map_err(|_| anyhow!("Failed to read keypair file"))?;
 
@@ -91,12 +173,145 @@ fn main() -> Result<()> {
 
     match &cli.command {
         Commands::Upload { program_path } => {
+            guard_mainnet_genesis(&rpc_client, expects_mainnet)?;
             upload_program(&rpc_client, &payer, program_path, &terminated)?;
         }
         Commands::Close { program_id } => {
+            guard_mainnet_genesis(&rpc_client, expects_mainnet)?;
             let pubkey: Pubkey = program_id.parse()?;
             process_close(&rpc_client, &payer, &pubkey, &terminated)?;
         }
+        Commands::Verify {
+            program_id,
+            source_path,
+        } => {
+            let pubkey: Pubkey = program_id.parse()?;
+            verify_program(&rpc_client, &pubkey, source_path)?;
+        }
+        Commands::PublishManifest {
+            program_id,
+            source_git_url,
+            commit_hash,
+            docker_image_tag,
+            build_sha256,
+        } => {
+            let pubkey: Pubkey = program_id.parse()?;
+            let timestamp_secs = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)?
+                .as_secs();
+            let manifest = UpdateManifest {
+                program_id: pubkey,
+                source_git_url: source_git_url.clone(),
+                commit_hash: commit_hash.clone(),
+                docker_image_tag: docker_image_tag.clone(),
+                build_sha256: build_sha256.clone(),
+                timestamp_secs,
+            };
+            publish_manifest(&rpc_client, &payer, manifest)?;
+        }
+        Commands::Download { url, sha256 } => {
+            guard_mainnet_genesis(&rpc_client, expects_mainnet)?;
+            let program_path = download_and_verify(url, sha256, &terminated)?;
+            upload_program(&rpc_client, &payer, &program_path, &terminated)?;
+        }
+        Commands::UploadAll { repo_path } => {
+            guard_mainnet_genesis(&rpc_client, expects_mainnet)?;
+            let workspace_root = find_workspace_root(repo_path)?;
+            let programs = discover_programs(&workspace_root)?;
+            if programs.is_empty() {
+                return Err(anyhow!("No built programs found under {}", workspace_root.display()));
+            }
+
+            for program in programs {
+                match upload_program(&rpc_client, &payer, &program.so_path, &terminated) {
+                    Ok(()) => println!("[ok] {} ({})", program.lib_name, program.program_id),
+                    Err(e) => println!("[failed] {} ({}): {e}", program.lib_name, program.program_id),
+                }
+            }
+        }
+        Commands::Serve {
+            listen_addr,
+            artifact_dir,
+        } => {
+            let addr = listen_addr
+                .parse()
+                .map_err(|e| anyhow!("Invalid listen address {listen_addr}: {e}"))?;
+            let state = ServeState {
+                rpc_client: RpcClient::new(rpc_url.clone()),
+                authority: payer.pubkey(),
+                artifact_dir: artifact_dir.clone(),
+            };
+            tokio::runtime::Runtime::new()?.block_on(serve(addr, state))?;
+        }
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with_rpc_url(json_rpc_url: &str) -> Config {
+        Config {
+            json_rpc_url: json_rpc_url.to_string(),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn resolve_network_url_accepts_aliases() {
+        let config = config_with_rpc_url("https://api.devnet.solana.com");
+        assert_eq!(
+            resolve_network_url(Some("mainnet"), &config).unwrap(),
+            ("https://api.mainnet-beta.solana.com".to_string(), true)
+        );
+        assert_eq!(
+            resolve_network_url(Some("dev"), &config).unwrap(),
+            ("https://api.devnet.solana.com".to_string(), false)
+        );
+        assert_eq!(
+            resolve_network_url(Some("t"), &config).unwrap(),
+            ("https://api.testnet.solana.com".to_string(), false)
+        );
+        assert_eq!(
+            resolve_network_url(Some("local"), &config).unwrap(),
+            ("http://127.0.0.1:8899".to_string(), false)
+        );
+    }
+
+    #[test]
+    fn resolve_network_url_accepts_raw_urls() {
+        let config = config_with_rpc_url("https://api.devnet.solana.com");
+        let (url, expects_mainnet) =
+            resolve_network_url(Some("https://my-rpc.example.com"), &config).unwrap();
+        assert_eq!(url, "https://my-rpc.example.com");
+        assert!(!expects_mainnet);
+    }
+
+    #[test]
+    fn resolve_network_url_rejects_unknown_alias() {
+        let config = config_with_rpc_url("https://api.devnet.solana.com");
+        assert!(resolve_network_url(Some("not-a-cluster"), &config).is_err());
+    }
+
+    #[test]
+    fn resolve_network_url_falls_back_to_cli_config_when_omitted() {
+        let config = config_with_rpc_url("https://api.mainnet-beta.solana.com");
+        assert_eq!(
+            resolve_network_url(None, &config).unwrap(),
+            ("https://api.mainnet-beta.solana.com".to_string(), true)
+        );
+
+        let config = config_with_rpc_url("http://127.0.0.1:8899");
+        assert_eq!(
+            resolve_network_url(None, &config).unwrap(),
+            ("http://127.0.0.1:8899".to_string(), false)
+        );
+    }
+
+    #[test]
+    fn guard_mainnet_genesis_skips_check_when_not_expecting_mainnet() {
+        let rpc_client = RpcClient::new("http://127.0.0.1:1".to_string());
+        assert!(guard_mainnet_genesis(&rpc_client, false).is_ok());
+    }
+}
@@ -0,0 +1,165 @@
+use anyhow::{anyhow, Result};
+use axum::{
+    body::Body,
+    extract::{Path as AxumPath, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use std::{net::SocketAddr, path::PathBuf, sync::Arc};
+
+use crate::verify_manifest::fetch_manifest_for_program;
+
+/// Shared state handed to every request handler: the RPC client and the authority
+/// manifests were published under (both needed to re-derive a program's manifest
+/// account), and the directory the `.so` artifacts are read from.
+pub struct ServeState {
+    pub rpc_client: RpcClient,
+    pub authority: Pubkey,
+    pub artifact_dir: PathBuf,
+}
+
+#[derive(Serialize)]
+struct VerificationStatus {
+    program_id: String,
+    source_git_url: String,
+    commit_hash: String,
+    docker_image_tag: String,
+    build_sha256: String,
+    timestamp_secs: u64,
+}
+
+/// Starts the verification HTTP server on `addr`, serving JSON status for a program's
+/// signed manifest and streaming its verified `.so` artifact with range-request support.
+pub async fn serve(addr: SocketAddr, state: ServeState) -> Result<()> {
+    let state = Arc::new(state);
+    let app = Router::new()
+        .route("/status/:program_id", get(status_handler))
+        .route("/artifact/:program_id", get(artifact_handler))
+        .with_state(state);
+
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .map_err(|e| anyhow!("Failed to bind {addr}: {e}"))?;
+    println!("Verification server listening on {addr}");
+    axum::serve(listener, app)
+        .await
+        .map_err(|e| anyhow!("Server error: {e}"))
+}
+
+async fn status_handler(
+    State(state): State<Arc<ServeState>>,
+    AxumPath(program_id): AxumPath<String>,
+) -> Response {
+    let pubkey: Pubkey = match program_id.parse() {
+        Ok(p) => p,
+        Err(_) => return (StatusCode::BAD_REQUEST, "invalid program id").into_response(),
+    };
+
+    match fetch_manifest_for_program(&state.rpc_client, &state.authority, &pubkey) {
+        Ok(signed) => Json(VerificationStatus {
+            program_id: signed.manifest.program_id.to_string(),
+            source_git_url: signed.manifest.source_git_url,
+            commit_hash: signed.manifest.commit_hash,
+            docker_image_tag: signed.manifest.docker_image_tag,
+            build_sha256: signed.manifest.build_sha256,
+            timestamp_secs: signed.manifest.timestamp_secs,
+        })
+        .into_response(),
+        Err(e) => (StatusCode::NOT_FOUND, e.to_string()).into_response(),
+    }
+}
+
+/// Streams `<artifact_dir>/<program_id>.so`, but only after confirming its sha256
+/// matches the program's signed manifest — this is what makes the file "canonical"
+/// rather than just whatever happens to be sitting in `artifact_dir`.
+async fn artifact_handler(
+    State(state): State<Arc<ServeState>>,
+    AxumPath(program_id): AxumPath<String>,
+    headers: HeaderMap,
+) -> Response {
+    let pubkey: Pubkey = match program_id.parse() {
+        Ok(p) => p,
+        Err(_) => return (StatusCode::BAD_REQUEST, "invalid program id").into_response(),
+    };
+
+    let manifest = match fetch_manifest_for_program(&state.rpc_client, &state.authority, &pubkey) {
+        Ok(m) => m.manifest,
+        Err(e) => return (StatusCode::NOT_FOUND, format!("no manifest for {program_id}: {e}")).into_response(),
+    };
+
+    let so_path = state.artifact_dir.join(format!("{program_id}.so"));
+    let bytes = match tokio::fs::read(&so_path).await {
+        Ok(b) => b,
+        Err(e) => return (StatusCode::NOT_FOUND, format!("artifact not found: {e}")).into_response(),
+    };
+
+    let actual_sha256 = hex::encode(Sha256::digest(&bytes));
+    if actual_sha256 != manifest.build_sha256 {
+        return (
+            StatusCode::CONFLICT,
+            format!(
+                "served artifact does not match signed manifest: expected {}, got {actual_sha256}",
+                manifest.build_sha256
+            ),
+        )
+            .into_response();
+    }
+
+    serve_bytes_with_range(&bytes, headers.get(header::RANGE))
+}
+
+/// Serves `bytes` honoring a single `Range: bytes=start-end` request header, so clients
+/// can fetch a slice of the artifact to spot-check the sha256 themselves.
+fn serve_bytes_with_range(bytes: &[u8], range_header: Option<&header::HeaderValue>) -> Response {
+    let total_len = bytes.len();
+    let range = range_header.and_then(|v| v.to_str().ok()).and_then(parse_byte_range);
+
+    match range {
+        Some((start, end)) if start <= end && end < total_len => {
+            let slice = bytes[start..=end].to_vec();
+            Response::builder()
+                .status(StatusCode::PARTIAL_CONTENT)
+                .header(header::CONTENT_TYPE, "application/octet-stream")
+                .header(header::ACCEPT_RANGES, "bytes")
+                .header(header::CONTENT_RANGE, format!("bytes {start}-{end}/{total_len}"))
+                .body(Body::from(slice))
+                .unwrap_or_else(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())
+        }
+        Some(_) => (StatusCode::RANGE_NOT_SATISFIABLE, "invalid range").into_response(),
+        None => Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, "application/octet-stream")
+            .header(header::ACCEPT_RANGES, "bytes")
+            .body(Body::from(bytes.to_vec()))
+            .unwrap_or_else(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response()),
+    }
+}
+
+/// Parses a simple `bytes=start-end` range header, the only form this server supports.
+fn parse_byte_range(header_value: &str) -> Option<(usize, usize)> {
+    let spec = header_value.strip_prefix("bytes=")?;
+    let (start, end) = spec.split_once('-')?;
+    Some((start.parse().ok()?, end.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_byte_range_parses_valid_header() {
+        assert_eq!(parse_byte_range("bytes=0-99"), Some((0, 99)));
+    }
+
+    #[test]
+    fn parse_byte_range_rejects_malformed_header() {
+        assert_eq!(parse_byte_range("chunks=0-99"), None);
+        assert_eq!(parse_byte_range("bytes=abc-99"), None);
+    }
+}
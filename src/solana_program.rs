@@ -0,0 +1,131 @@
+use crate::api::send_job_to_remote;
+use anyhow::{anyhow, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    bpf_loader_upgradeable,
+    commitment_config::CommitmentConfig,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+use std::{
+    fs::File,
+    io::Read,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// Number of program bytes written per `Write` instruction. Kept well under the
+/// transaction size limit once the surrounding instruction/account overhead is added.
+const CHUNK_SIZE: usize = 900;
+
+/// Uploads a built `.so` into a fresh buffer account so it can later be deployed or
+/// upgraded, then hands the buffer off to the remote verifier so an independent build
+/// can confirm its bytes match the published source.
+pub fn upload_program(
+    rpc_client: &RpcClient,
+    payer: &Keypair,
+    program_path: &PathBuf,
+    terminated: &Arc<AtomicBool>,
+) -> Result<()> {
+    let mut file = File::open(program_path)
+        .map_err(|e| anyhow!("Failed to open program at {}: {e}", program_path.display()))?;
+    let mut program_bytes = Vec::new();
+    file.read_to_end(&mut program_bytes)?;
+
+    let buffer_keypair = Keypair::new();
+    let buffer_len = bpf_loader_upgradeable::UpgradeableLoaderState::size_of_buffer(program_bytes.len());
+    let rent = rpc_client.get_minimum_balance_for_rent_exemption(buffer_len)?;
+
+    let create_buffer_ixs = bpf_loader_upgradeable::create_buffer(
+        &payer.pubkey(),
+        &buffer_keypair.pubkey(),
+        &payer.pubkey(),
+        rent,
+        program_bytes.len(),
+    )?;
+
+    let blockhash = rpc_client.get_latest_blockhash()?;
+    let create_tx = Transaction::new_signed_with_payer(
+        &create_buffer_ixs,
+        Some(&payer.pubkey()),
+        &[payer, &buffer_keypair],
+        blockhash,
+    );
+    rpc_client.send_and_confirm_transaction_with_spinner(&create_tx)?;
+
+    let pb = ProgressBar::new(program_bytes.len() as u64);
+    pb.set_style(
+        ProgressStyle::with_template("{bar:40.cyan/blue} {bytes}/{total_bytes} {msg}")
+            .unwrap_or_else(|_| ProgressStyle::default_bar()),
+    );
+
+    for (offset, chunk) in program_bytes.chunks(CHUNK_SIZE).enumerate() {
+        if terminated.load(Ordering::Relaxed) {
+            pb.abandon_with_message("aborted");
+            return Err(anyhow!("Upload interrupted, buffer {} is left partially written", buffer_keypair.pubkey()));
+        }
+
+        let write_ix = bpf_loader_upgradeable::write(
+            &buffer_keypair.pubkey(),
+            &payer.pubkey(),
+            (offset * CHUNK_SIZE) as u32,
+            chunk.to_vec(),
+        );
+        let blockhash = rpc_client.get_latest_blockhash()?;
+        let write_tx = Transaction::new_signed_with_payer(
+            &[write_ix],
+            Some(&payer.pubkey()),
+            &[payer],
+            blockhash,
+        );
+        rpc_client.send_and_confirm_transaction(&write_tx)?;
+        pb.inc(chunk.len() as u64);
+    }
+    pb.finish_with_message("uploaded");
+
+    println!("Uploaded program to buffer {}", buffer_keypair.pubkey());
+    if let Err(e) = send_job_to_remote(&buffer_keypair.pubkey()) {
+        eprintln!("Warning: {e}");
+    }
+    Ok(())
+}
+
+/// Closes a buffer account, reclaiming its rent to `payer`. No program account is
+/// passed to the loader's close instruction, so program-data accounts are not closed.
+pub fn process_close(
+    rpc_client: &RpcClient,
+    payer: &Keypair,
+    account: &Pubkey,
+    terminated: &Arc<AtomicBool>,
+) -> Result<()> {
+    if terminated.load(Ordering::Relaxed) {
+        return Err(anyhow!("Close aborted before submission"));
+    }
+
+    rpc_client.get_account_with_commitment(account, CommitmentConfig::confirmed())?
+        .value
+        .ok_or_else(|| anyhow!("Account {account} does not exist"))?;
+
+    let close_ix = bpf_loader_upgradeable::close_any(
+        account,
+        &payer.pubkey(),
+        Some(&payer.pubkey()),
+        None,
+    );
+    let blockhash = rpc_client.get_latest_blockhash()?;
+    let tx = Transaction::new_signed_with_payer(
+        &[close_ix],
+        Some(&payer.pubkey()),
+        &[payer],
+        blockhash,
+    );
+    rpc_client.send_and_confirm_transaction_with_spinner(&tx)?;
+
+    println!("Closed {account}, rent reclaimed to {}", payer.pubkey());
+    Ok(())
+}
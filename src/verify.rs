@@ -0,0 +1,133 @@
+use anyhow::{anyhow, Result};
+use sha2::{Digest, Sha256};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    bpf_loader_upgradeable::UpgradeableLoaderState, bpf_loader_upgradeable, pubkey::Pubkey,
+};
+use std::{
+    fs,
+    path::Path,
+    process::{Command, Stdio},
+};
+
+use crate::image_config::IMAGE_MAP;
+
+/// Toolchain version whose pinned image every build uses; the source tree's own
+/// toolchain files (such as `rust-toolchain.toml`) are not read.
+const DEFAULT_TOOLCHAIN: &str = "v1.16.0";
+
+/// Runs a deterministic build of `source_path` inside the pinned Docker image, fetches
+/// the bytecode currently deployed at `program_id`, and reports whether the two match.
+pub fn verify_program(rpc_client: &RpcClient, program_id: &Pubkey, source_path: &Path) -> Result<()> {
+    let built_so = build_in_docker(source_path)?;
+    let built_bytes = fs::read(&built_so)
+        .map_err(|e| anyhow!("Failed to read build output at {}: {e}", built_so.display()))?;
+    let built_hash = hex::encode(Sha256::digest(&built_bytes));
+
+    let onchain_bytes = fetch_onchain_executable(rpc_client, program_id)?;
+    let onchain_hash = hex::encode(Sha256::digest(&onchain_bytes));
+
+    if built_hash == onchain_hash {
+        println!("Verified OK: {program_id} matches local build");
+        println!("  sha256: {built_hash}");
+    } else {
+        println!("Verification FAILED for {program_id}");
+        println!("  build:   {built_hash}");
+        println!("  on-chain: {onchain_hash}");
+        return Err(anyhow!("build hash does not match on-chain program"));
+    }
+    Ok(())
+}
+
+/// Builds `source_path` inside the Docker image pinned for `DEFAULT_TOOLCHAIN` and
+/// returns the path to the resulting `.so` on the host.
+fn build_in_docker(source_path: &Path) -> Result<std::path::PathBuf> {
+    let image = IMAGE_MAP
+        .get(DEFAULT_TOOLCHAIN)
+        .ok_or_else(|| anyhow!("No pinned Docker image for toolchain {DEFAULT_TOOLCHAIN}"))?;
+
+    // Docker's bind-mount parsing needs an absolute host path: a relative one (the
+    // natural way to pass `--source-path .`) can be rejected outright or silently
+    // reinterpreted as a named volume, building against an empty directory instead.
+    let source_path = fs::canonicalize(source_path)
+        .map_err(|e| anyhow!("Failed to resolve source path {}: {e}", source_path.display()))?;
+
+    let status = Command::new("docker")
+        .args([
+            "run",
+            "--rm",
+            "-v",
+            &format!("{}:/workdir", source_path.display()),
+            "-w",
+            "/workdir",
+            image,
+            "cargo",
+            "build-sbf",
+        ])
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .map_err(|e| anyhow!("Failed to run docker: {e}"))?;
+
+    if !status.success() {
+        return Err(anyhow!("Docker build failed with status {status}"));
+    }
+
+    let deploy_dir = source_path.join("target").join("deploy");
+    let so_file = fs::read_dir(&deploy_dir)
+        .map_err(|e| anyhow!("Failed to read {}: {e}", deploy_dir.display()))?
+        .filter_map(|entry| entry.ok())
+        .find(|entry| entry.path().extension().map(|ext| ext == "so").unwrap_or(false))
+        .ok_or_else(|| anyhow!("No .so file produced in {}", deploy_dir.display()))?
+        .path();
+
+    Ok(so_file)
+}
+
+/// Fetches the `ProgramData` account for `program_id` and returns the executable bytes
+/// that follow the `UpgradeableLoaderState` header.
+fn fetch_onchain_executable(rpc_client: &RpcClient, program_id: &Pubkey) -> Result<Vec<u8>> {
+    let program_account = rpc_client.get_account(program_id)?;
+    let programdata_address = match bincode::deserialize(&program_account.data)? {
+        UpgradeableLoaderState::Program {
+            programdata_address,
+        } => programdata_address,
+        _ => return Err(anyhow!("{program_id} is not an upgradeable program")),
+    };
+
+    let programdata_account = rpc_client.get_account(&programdata_address)?;
+    let header_len = UpgradeableLoaderState::size_of_programdata_metadata();
+    if programdata_account.data.len() < header_len {
+        return Err(anyhow!("ProgramData account for {program_id} is smaller than its header"));
+    }
+
+    // The loader allocates `ProgramData` with room to spare for future upgrades and
+    // zero-pads the tail, so trim back to the actual ELF before hashing or a verified
+    // build would never match.
+    Ok(trim_trailing_zeros(&programdata_account.data[header_len..]))
+}
+
+fn trim_trailing_zeros(bytes: &[u8]) -> Vec<u8> {
+    let end = bytes.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+    bytes[..end].to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trim_trailing_zeros_strips_padding() {
+        assert_eq!(trim_trailing_zeros(&[1, 2, 3, 0, 0, 0]), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn trim_trailing_zeros_keeps_interior_zeros() {
+        assert_eq!(trim_trailing_zeros(&[1, 0, 2, 0]), vec![1, 0, 2]);
+    }
+
+    #[test]
+    fn trim_trailing_zeros_all_zero_is_empty() {
+        assert_eq!(trim_trailing_zeros(&[0, 0, 0]), Vec::<u8>::new());
+    }
+}
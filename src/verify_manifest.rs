@@ -0,0 +1,270 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    bpf_loader_upgradeable,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+    system_instruction,
+    transaction::Transaction,
+};
+
+/// `Pubkey::create_with_seed` seeds are capped at `solana_sdk::pubkey::MAX_SEED_LEN` (32)
+/// bytes, so this prefix plus a truncated base58 program id has to fit comfortably under
+/// that ceiling.
+const MANIFEST_SEED_PREFIX: &str = "vm:";
+const MANIFEST_SEED_ID_LEN: usize = 28;
+
+/// The unsigned contents of a verification manifest: everything needed to say "this
+/// program id, built from this commit, inside this Docker image, hashes to this sha256".
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UpdateManifest {
+    pub program_id: Pubkey,
+    pub source_git_url: String,
+    pub commit_hash: String,
+    pub docker_image_tag: String,
+    pub build_sha256: String,
+    pub timestamp_secs: u64,
+}
+
+/// An `UpdateManifest` plus a signature over its bincode encoding, so anyone holding the
+/// manifest can confirm it was produced by the holder of `signer`'s private key without
+/// having to trust whoever is serving it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SignedUpdateManifest {
+    pub manifest: UpdateManifest,
+    pub signer: Pubkey,
+    pub signature: Signature,
+}
+
+/// Types that can be turned into a signed, verifiable envelope.
+pub trait Signable {
+    fn sign(self, keypair: &Keypair) -> Result<SignedUpdateManifest>;
+}
+
+impl Signable for UpdateManifest {
+    fn sign(self, keypair: &Keypair) -> Result<SignedUpdateManifest> {
+        let bytes = bincode::serialize(&self)?;
+        let signature = keypair.sign_message(&bytes);
+        Ok(SignedUpdateManifest {
+            manifest: self,
+            signer: keypair.pubkey(),
+            signature,
+        })
+    }
+}
+
+impl SignedUpdateManifest {
+    /// Deserializes a `SignedUpdateManifest` from account data and rejects it unless the
+    /// embedded signature actually verifies against the embedded signer and manifest bytes.
+    pub fn deserialize_and_verify(data: &[u8]) -> Result<Self> {
+        let signed: SignedUpdateManifest = bincode::deserialize(data)
+            .map_err(|e| anyhow!("Failed to decode manifest account: {e}"))?;
+
+        let manifest_bytes = bincode::serialize(&signed.manifest)?;
+        if !signed
+            .signature
+            .verify(signed.signer.as_ref(), &manifest_bytes)
+        {
+            return Err(anyhow!(
+                "Manifest signature does not match signer {}",
+                signed.signer
+            ));
+        }
+
+        Ok(signed)
+    }
+}
+
+/// The seed a program id's manifest account is derived with under a given authority.
+/// Truncated because `Pubkey::create_with_seed` seeds are capped at 32 bytes.
+fn manifest_seed(program_id: &Pubkey) -> String {
+    let id = program_id.to_string();
+    format!("{MANIFEST_SEED_PREFIX}{}", &id[..id.len().min(MANIFEST_SEED_ID_LEN)])
+}
+
+/// Deterministically derives the manifest account for `program_id` published by
+/// `authority`. Unlike a PDA (which only the owning program could sign for), this uses
+/// `Pubkey::create_with_seed`, so the authority itself can create and write it directly
+/// — and anyone who knows the authority can recompute the same address to look it up,
+/// giving a real `program_id -> manifest_account` mapping without a bespoke on-chain
+/// program.
+pub fn derive_manifest_address(authority: &Pubkey, program_id: &Pubkey) -> Result<Pubkey> {
+    Pubkey::create_with_seed(authority, &manifest_seed(program_id), &bpf_loader_upgradeable::id())
+        .map_err(|e| anyhow!("Failed to derive manifest address: {e}"))
+}
+
+/// Publishes a signed manifest on-chain at the address `derive_manifest_address` returns
+/// for `(payer, manifest.program_id)`, so the manifest for a program is always
+/// rediscoverable from just its program id and publishing authority.
+pub fn publish_manifest(
+    rpc_client: &RpcClient,
+    payer: &Keypair,
+    manifest: UpdateManifest,
+) -> Result<Pubkey> {
+    let signed = manifest.sign(payer)?;
+    let data = bincode::serialize(&signed)?;
+
+    let manifest_address = derive_manifest_address(&payer.pubkey(), &signed.manifest.program_id)?;
+    let seed = manifest_seed(&signed.manifest.program_id);
+    let account_len = bpf_loader_upgradeable::UpgradeableLoaderState::size_of_buffer(data.len());
+    let rent = rpc_client.get_minimum_balance_for_rent_exemption(account_len)?;
+
+    let create_ix = system_instruction::create_account_with_seed(
+        &payer.pubkey(),
+        &manifest_address,
+        &payer.pubkey(),
+        &seed,
+        rent,
+        account_len as u64,
+        &bpf_loader_upgradeable::id(),
+    );
+    let init_ix = bpf_loader_upgradeable::initialize_buffer(&manifest_address, &payer.pubkey());
+    let blockhash = rpc_client.get_latest_blockhash()?;
+    let create_tx = Transaction::new_signed_with_payer(
+        &[create_ix, init_ix],
+        Some(&payer.pubkey()),
+        &[payer],
+        blockhash,
+    );
+    rpc_client.send_and_confirm_transaction_with_spinner(&create_tx)?;
+
+    let write_ix = bpf_loader_upgradeable::write(&manifest_address, &payer.pubkey(), 0, data);
+    let blockhash = rpc_client.get_latest_blockhash()?;
+    let write_tx = Transaction::new_signed_with_payer(
+        &[write_ix],
+        Some(&payer.pubkey()),
+        &[payer],
+        blockhash,
+    );
+    rpc_client.send_and_confirm_transaction(&write_tx)?;
+
+    println!(
+        "Published signed manifest for {} at {manifest_address}",
+        signed.manifest.program_id,
+    );
+    Ok(manifest_address)
+}
+
+/// Decodes a `SignedUpdateManifest` out of raw `Buffer` account data.
+///
+/// `publish_manifest` writes through the loader's `Buffer` account format, so the bytes
+/// read back are `[Buffer{authority} header][manifest bytes]`, not the manifest alone —
+/// skip the header (mirroring how `verify.rs::fetch_onchain_executable` skips the
+/// `ProgramData` header) before handing the rest to bincode.
+fn decode_manifest_account(data: &[u8]) -> Result<SignedUpdateManifest> {
+    let header_len = bpf_loader_upgradeable::UpgradeableLoaderState::size_of_buffer_metadata();
+    if data.len() < header_len {
+        return Err(anyhow!("Manifest account data is smaller than its buffer header"));
+    }
+    SignedUpdateManifest::deserialize_and_verify(&data[header_len..])
+}
+
+/// Fetches and verifies the signed manifest stored at `manifest_account`.
+pub fn fetch_manifest(rpc_client: &RpcClient, manifest_account: &Pubkey) -> Result<SignedUpdateManifest> {
+    let account = rpc_client.get_account(manifest_account)?;
+    decode_manifest_account(&account.data)
+}
+
+/// Fetches and verifies the signed manifest published for `program_id` by `authority`,
+/// re-deriving the manifest account instead of requiring the caller to already know it.
+pub fn fetch_manifest_for_program(
+    rpc_client: &RpcClient,
+    authority: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<SignedUpdateManifest> {
+    let manifest_address = derive_manifest_address(authority, program_id)?;
+    fetch_manifest(rpc_client, &manifest_address)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_then_verify_round_trips() {
+        let keypair = Keypair::new();
+        let manifest = UpdateManifest {
+            program_id: Pubkey::new_unique(),
+            source_git_url: "https://github.com/example/program".to_string(),
+            commit_hash: "deadbeef".to_string(),
+            docker_image_tag: "ellipsislabs/solana:1.16.0".to_string(),
+            build_sha256: "0".repeat(64),
+            timestamp_secs: 1_700_000_000,
+        };
+
+        let signed = manifest.clone().sign(&keypair).unwrap();
+        let bytes = bincode::serialize(&signed).unwrap();
+        let verified = SignedUpdateManifest::deserialize_and_verify(&bytes).unwrap();
+
+        assert_eq!(verified.manifest, manifest);
+        assert_eq!(verified.signer, keypair.pubkey());
+    }
+
+    #[test]
+    fn verify_rejects_tampered_manifest() {
+        let keypair = Keypair::new();
+        let manifest = UpdateManifest {
+            program_id: Pubkey::new_unique(),
+            source_git_url: "https://github.com/example/program".to_string(),
+            commit_hash: "deadbeef".to_string(),
+            docker_image_tag: "ellipsislabs/solana:1.16.0".to_string(),
+            build_sha256: "0".repeat(64),
+            timestamp_secs: 1_700_000_000,
+        };
+
+        let mut signed = manifest.sign(&keypair).unwrap();
+        signed.manifest.build_sha256 = "1".repeat(64);
+        let bytes = bincode::serialize(&signed).unwrap();
+
+        assert!(SignedUpdateManifest::deserialize_and_verify(&bytes).is_err());
+    }
+
+    #[test]
+    fn decode_manifest_account_skips_buffer_header() {
+        let keypair = Keypair::new();
+        let manifest = UpdateManifest {
+            program_id: Pubkey::new_unique(),
+            source_git_url: "https://github.com/example/program".to_string(),
+            commit_hash: "deadbeef".to_string(),
+            docker_image_tag: "ellipsislabs/solana:1.16.0".to_string(),
+            build_sha256: "0".repeat(64),
+            timestamp_secs: 1_700_000_000,
+        };
+        let signed = manifest.clone().sign(&keypair).unwrap();
+        let manifest_bytes = bincode::serialize(&signed).unwrap();
+
+        // Lay the bytes out exactly as `initialize_buffer` + `write` would on-chain:
+        // a `Buffer{authority}` header followed immediately by the written data.
+        let header = bincode::serialize(&bpf_loader_upgradeable::UpgradeableLoaderState::Buffer {
+            authority_address: Some(keypair.pubkey()),
+        })
+        .unwrap();
+        let header_len = bpf_loader_upgradeable::UpgradeableLoaderState::size_of_buffer_metadata();
+        let mut account_data = vec![0u8; header_len];
+        account_data[..header.len()].copy_from_slice(&header);
+        account_data.extend_from_slice(&manifest_bytes);
+
+        let decoded = decode_manifest_account(&account_data).unwrap();
+        assert_eq!(decoded.manifest, manifest);
+    }
+
+    #[test]
+    fn decode_manifest_account_rejects_data_shorter_than_header() {
+        assert!(decode_manifest_account(&[0u8; 4]).is_err());
+    }
+
+    #[test]
+    fn derive_manifest_address_is_deterministic_per_program() {
+        let authority = Pubkey::new_unique();
+        let program_a = Pubkey::new_unique();
+        let program_b = Pubkey::new_unique();
+
+        let a1 = derive_manifest_address(&authority, &program_a).unwrap();
+        let a2 = derive_manifest_address(&authority, &program_a).unwrap();
+        let b = derive_manifest_address(&authority, &program_b).unwrap();
+
+        assert_eq!(a1, a2);
+        assert_ne!(a1, b);
+    }
+}
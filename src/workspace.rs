@@ -0,0 +1,162 @@
+use anyhow::{anyhow, Result};
+use cargo_toml::Manifest;
+use solana_sdk::signature::{read_keypair_file, Signer};
+use std::path::{Path, PathBuf};
+
+/// Expands a workspace's `members` entries, resolving glob patterns like `programs/*`
+/// (the form every real Anchor workspace uses) in addition to literal paths.
+fn expand_members(workspace_root: &Path, members: &[String]) -> Result<Vec<PathBuf>> {
+    let mut resolved = Vec::new();
+    for member in members {
+        if member.contains('*') {
+            let pattern = workspace_root.join(member);
+            let pattern_str = pattern
+                .to_str()
+                .ok_or_else(|| anyhow!("Non-UTF8 workspace member pattern {member}"))?;
+            for entry in glob::glob(pattern_str)
+                .map_err(|e| anyhow!("Invalid workspace member glob {member}: {e}"))?
+            {
+                let path = entry.map_err(|e| anyhow!("Failed to read glob match: {e}"))?;
+                if path.join("Cargo.toml").exists() {
+                    resolved.push(path);
+                }
+            }
+        } else {
+            resolved.push(workspace_root.join(member));
+        }
+    }
+    Ok(resolved)
+}
+
+/// A single on-chain program discovered in a workspace: its crate's lib name (used to
+/// locate the build artifacts under `target/deploy`) and its declared program id.
+#[derive(Debug, Clone)]
+pub struct DiscoveredProgram {
+    pub lib_name: String,
+    pub program_id: String,
+    pub so_path: PathBuf,
+}
+
+/// Walks up from `start_path` looking for the workspace root (a `Cargo.toml` or
+/// `Anchor.toml`), mirroring Anchor's own `find_cargo_toml` lookup.
+pub fn find_workspace_root(start_path: &Path) -> Result<PathBuf> {
+    let mut dir = if start_path.is_dir() {
+        start_path.to_path_buf()
+    } else {
+        start_path
+            .parent()
+            .ok_or_else(|| anyhow!("{} has no parent directory", start_path.display()))?
+            .to_path_buf()
+    };
+
+    loop {
+        if dir.join("Anchor.toml").exists() || dir.join("Cargo.toml").exists() {
+            return Ok(dir);
+        }
+        if !dir.pop() {
+            return Err(anyhow!(
+                "Could not find a Cargo.toml or Anchor.toml above {}",
+                start_path.display()
+            ));
+        }
+    }
+}
+
+/// Discovers every on-chain program in the workspace rooted at `workspace_root`: reads
+/// the workspace member list, then for each member with a built artifact under
+/// `target/deploy`, resolves its lib name, `.so` path, and declared program id from the
+/// paired `<lib>-keypair.json`.
+pub fn discover_programs(workspace_root: &Path) -> Result<Vec<DiscoveredProgram>> {
+    let manifest = Manifest::from_path(workspace_root.join("Cargo.toml"))
+        .map_err(|e| anyhow!("Failed to parse {}/Cargo.toml: {e}", workspace_root.display()))?;
+
+    let members = manifest
+        .workspace
+        .ok_or_else(|| anyhow!("{}/Cargo.toml has no [workspace] section", workspace_root.display()))?
+        .members;
+
+    let deploy_dir = workspace_root.join("target").join("deploy");
+    let mut programs = Vec::new();
+
+    for member_path in expand_members(workspace_root, &members)? {
+        let member_manifest = Manifest::from_path(member_path.join("Cargo.toml")).map_err(|e| {
+            anyhow!("Failed to parse workspace member {}: {e}", member_path.display())
+        })?;
+        let Some(lib) = member_manifest.lib else {
+            continue;
+        };
+        let Some(lib_name) = lib.name else {
+            continue;
+        };
+
+        let so_path = deploy_dir.join(format!("{lib_name}.so"));
+        let keypair_path = deploy_dir.join(format!("{lib_name}-keypair.json"));
+        if !so_path.exists() || !keypair_path.exists() {
+            continue;
+        }
+
+        let program_id = read_keypair_file(&keypair_path)
+            .map_err(|_| anyhow!("Failed to read keypair at {}", keypair_path.display()))?
+            .pubkey()
+            .to_string();
+
+        programs.push(DiscoveredProgram {
+            lib_name,
+            program_id,
+            so_path,
+        });
+    }
+
+    Ok(programs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_workspace_root_walks_up_to_cargo_toml() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::write(root.path().join("Cargo.toml"), "[workspace]\nmembers = []\n").unwrap();
+        let nested = root.path().join("programs").join("foo").join("src");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        let found = find_workspace_root(&nested).unwrap();
+        assert_eq!(found, root.path());
+    }
+
+    #[test]
+    fn find_workspace_root_errors_when_nothing_above() {
+        let root = tempfile::tempdir().unwrap();
+        let nested = root.path().join("a").join("b");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        assert!(find_workspace_root(&nested).is_err());
+    }
+
+    #[test]
+    fn expand_members_resolves_glob_and_literal_entries() {
+        let root = tempfile::tempdir().unwrap();
+        for name in ["programs/alpha", "programs/beta", "cli"] {
+            std::fs::create_dir_all(root.path().join(name)).unwrap();
+            std::fs::write(root.path().join(name).join("Cargo.toml"), "").unwrap();
+        }
+
+        let members = vec!["programs/*".to_string(), "cli".to_string()];
+        let mut resolved = expand_members(root.path(), &members)
+            .unwrap()
+            .into_iter()
+            .map(|p| p.strip_prefix(root.path()).unwrap().to_path_buf())
+            .collect::<Vec<_>>();
+        resolved.sort();
+
+        assert_eq!(
+            resolved,
+            vec![
+                PathBuf::from("cli"),
+                PathBuf::from("programs/alpha"),
+                PathBuf::from("programs/beta"),
+            ]
+        );
+    }
+}